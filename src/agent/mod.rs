@@ -0,0 +1,107 @@
+//! An `ssh-agent` protocol backend whose identities live on a Yubikey's PIV
+//! slots instead of on disk: `SSH_AGENTC_REQUEST_IDENTITIES` lists the
+//! certificates found across slots, and `SSH_AGENTC_SIGN_REQUEST` delegates
+//! the signature to whichever slot holds the matching key, so the private
+//! key material never leaves the hardware token.
+
+pub mod proto;
+
+use std::io;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
+
+use crate::ssh::{VecWriter, Writer};
+use crate::yubikey::ssh::{ssh_cert_fetch_pubkey, ssh_cert_signer};
+use crate::yubikey::{fetch_subject, RetiredSlotId, SlotId};
+
+use proto::{read_message, write_message, Request, Response};
+
+const NORMAL_SLOTS: [u8; 4] = [0x9a, 0x9c, 0x9d, 0x9e];
+const RETIRED_SLOTS: std::ops::Range<u8> = 0x82..0x96;
+
+/// Serves the `ssh-agent` protocol over a unix socket, backed by whatever
+/// certificates are present across the Yubikey's PIV slots.
+#[derive(Debug, Default)]
+pub struct Agent;
+
+impl Agent {
+    /// Creates a new `Agent`.
+    pub fn new() -> Agent {
+        Agent
+    }
+
+    /// Binds `socket_path` and serves connections until the process exits
+    /// or a connection errors out.
+    pub fn listen(&self, socket_path: &Path) -> io::Result<()> {
+        let _ = std::fs::remove_file(socket_path);
+        let listener = UnixListener::bind(socket_path)?;
+
+        for stream in listener.incoming() {
+            self.handle_client(stream?)?;
+        }
+
+        Ok(())
+    }
+
+    fn handle_client(&self, mut stream: UnixStream) -> io::Result<()> {
+        loop {
+            let body = match read_message(&mut stream) {
+                Ok(body) => body,
+                Err(_) => return Ok(()),
+            };
+
+            let response = match Request::decode(&body) {
+                Ok(Request::RequestIdentities) => Response::Identities(self.identities()),
+                Ok(Request::SignRequest { key_blob, data, .. }) => match self.sign(&key_blob, &data) {
+                    Ok(signature) => Response::SignResponse(signature),
+                    Err(_) => Response::Failure,
+                },
+                Ok(Request::Unsupported(_)) | Err(_) => Response::Failure,
+            };
+
+            write_message(&mut stream, &response.encode()?)?;
+        }
+    }
+
+    fn slots(&self) -> Vec<SlotId> {
+        let mut slots: Vec<SlotId> = NORMAL_SLOTS.iter().map(|&b| SlotId::from(b)).collect();
+        slots.extend(RETIRED_SLOTS.map(|b| SlotId::Retired(RetiredSlotId::from(b))));
+        slots
+    }
+
+    /// Lists the `(key blob, comment)` pairs currently available, one per
+    /// PIV slot holding a certificate.
+    fn identities(&self) -> Vec<(Vec<u8>, String)> {
+        self.slots()
+            .into_iter()
+            .filter_map(|slot| {
+                let cert = ssh_cert_fetch_pubkey(slot)?;
+                let comment = fetch_subject(slot).unwrap_or_default();
+
+                let mut writer = VecWriter::new();
+                writer.write_pub_key(&cert.key).ok()?;
+                Some((writer.into_bytes(), comment))
+            })
+            .collect()
+    }
+
+    /// Finds the slot whose public key matches `key_blob` and asks the
+    /// Yubikey to sign `data` with it.
+    fn sign(&self, key_blob: &[u8], data: &[u8]) -> io::Result<Vec<u8>> {
+        let slot = self
+            .slots()
+            .into_iter()
+            .find(|&slot| {
+                ssh_cert_fetch_pubkey(slot)
+                    .and_then(|cert| {
+                        let mut writer = VecWriter::new();
+                        writer.write_pub_key(&cert.key).ok()?;
+                        Some(writer.into_bytes() == key_blob)
+                    })
+                    .unwrap_or(false)
+            })
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no matching slot for key"))?;
+
+        ssh_cert_signer(slot, data)
+    }
+}
@@ -0,0 +1,110 @@
+//! Wire framing for the `ssh-agent` protocol (draft-miller-ssh-agent), built
+//! on the crate's own length-prefixed `Reader`/`Writer` encoding.
+
+use std::io;
+
+use crate::ssh::{ReadError, Reader, VecWriter, Writer};
+
+pub const SSH_AGENT_FAILURE: u8 = 5;
+pub const SSH_AGENTC_REQUEST_IDENTITIES: u8 = 11;
+pub const SSH_AGENT_IDENTITIES_ANSWER: u8 = 12;
+pub const SSH_AGENTC_SIGN_REQUEST: u8 = 13;
+pub const SSH_AGENT_SIGN_RESPONSE: u8 = 14;
+
+/// A request read off the agent socket, past the leading `u32` frame length.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Request {
+    RequestIdentities,
+    SignRequest { key_blob: Vec<u8>, data: Vec<u8>, flags: u32 },
+    Unsupported(u8),
+}
+
+impl Request {
+    /// Decodes a single agent request from its framed body.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use rustica_keys::agent::proto::{Request, SSH_AGENTC_REQUEST_IDENTITIES};
+    /// let body = [SSH_AGENTC_REQUEST_IDENTITIES];
+    /// assert_eq!(Request::decode(&body).unwrap(), Request::RequestIdentities);
+    /// ```
+    pub fn decode(body: &[u8]) -> io::Result<Request> {
+        let (&msg_type, rest) = body
+            .split_first()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "empty agent message"))?;
+
+        match msg_type {
+            SSH_AGENTC_REQUEST_IDENTITIES => Ok(Request::RequestIdentities),
+            SSH_AGENTC_SIGN_REQUEST => {
+                let mut reader = Reader::new(rest);
+                let key_blob = reader.read_bytes().map_err(to_io_error)?;
+                let data = reader.read_bytes().map_err(to_io_error)?;
+                let flags = reader.read_u32().map_err(to_io_error)?;
+                Ok(Request::SignRequest { key_blob, data, flags })
+            }
+            other => Ok(Request::Unsupported(other)),
+        }
+    }
+}
+
+/// A response ready to be framed and written back to the agent socket.
+#[derive(Debug)]
+pub enum Response {
+    Identities(Vec<(Vec<u8>, String)>),
+    SignResponse(Vec<u8>),
+    Failure,
+}
+
+impl Response {
+    /// Encodes this response into its framed body (without the leading
+    /// `u32` frame length, which the caller adds when writing to the wire).
+    ///
+    /// # Example
+    /// ```rust
+    /// # use rustica_keys::agent::proto::{Response, SSH_AGENT_SIGN_RESPONSE};
+    /// let body = Response::SignResponse(vec![1, 2, 3]).encode().unwrap();
+    /// assert_eq!(body[0], SSH_AGENT_SIGN_RESPONSE);
+    /// ```
+    pub fn encode(&self) -> io::Result<Vec<u8>> {
+        let mut body = VecWriter::new();
+
+        match self {
+            Response::Failure => body.write_u8(SSH_AGENT_FAILURE)?,
+            Response::Identities(identities) => {
+                body.write_u8(SSH_AGENT_IDENTITIES_ANSWER)?;
+                body.write_u32(identities.len() as u32)?;
+                for (key_blob, comment) in identities {
+                    body.write_bytes(key_blob)?;
+                    body.write_string(comment)?;
+                }
+            }
+            Response::SignResponse(signature) => {
+                body.write_u8(SSH_AGENT_SIGN_RESPONSE)?;
+                body.write_bytes(signature)?;
+            }
+        }
+
+        Ok(body.into_bytes())
+    }
+}
+
+fn to_io_error(e: ReadError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e.to_string())
+}
+
+/// Reads one `u32`-length-prefixed agent message off `stream`.
+pub fn read_message<R: io::Read>(stream: &mut R) -> io::Result<Vec<u8>> {
+    let mut len_buf = [0u8; 4];
+    stream.read_exact(&mut len_buf)?;
+    let len = u32::from_be_bytes(len_buf) as usize;
+
+    let mut body = vec![0u8; len];
+    stream.read_exact(&mut body)?;
+    Ok(body)
+}
+
+/// Writes `body` to `stream` as a `u32`-length-prefixed agent message.
+pub fn write_message<W: io::Write>(stream: &mut W, body: &[u8]) -> io::Result<()> {
+    stream.write_all(&(body.len() as u32).to_be_bytes())?;
+    stream.write_all(body)
+}
@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+
+use rustica_keys::ssh::{VecWriter, Writer};
+use rustica_keys::yubikey::ssh::{ssh_cert_fetch_pubkey, ssh_cert_signer};
+use rustica_keys::yubikey::{fetch_attestation, fetch_subject, RetiredSlotId, SlotId};
+
+const NORMAL_SLOTS: [u8; 4] = [0x9a, 0x9c, 0x9d, 0x9e];
+const RETIRED_SLOTS: std::ops::Range<u8> = 0x82..0x96;
+
+fn help() {
+    println!("sshcerts: generate and sign SSH certificates using a Yubikey PIV slot as the CA");
+    println!("Usage:");
+    println!("    sshcerts fingerprint");
+    println!("    sshcerts sign --ca 0x9c --pubkey <file> --principal <name> [--principal <name> ...]");
+    println!("                  [--valid-from <unix-time>] [--valid-to <unix-time>]");
+    println!("                  [--critical-option key=value ...] [--extension key=value ...]");
+    println!("    sshcerts attest --slot 0x9c");
+}
+
+/// Parses a `0x<slot>` argument into the PIV slot it addresses.
+fn parse_slot(value: &str) -> Option<SlotId> {
+    let hex = value.strip_prefix("0x")?;
+    let byte = u8::from_str_radix(hex, 16).ok()?;
+    Some(SlotId::from(byte))
+}
+
+/// Parses the repeated `--principal`, `--critical-option` and `--extension`
+/// flags, plus the handful of single-valued ones, out of a subcommand's
+/// argument list.
+struct SignArgs {
+    ca: SlotId,
+    pubkey_path: String,
+    principals: Vec<String>,
+    valid_from: u64,
+    valid_to: u64,
+    critical_options: HashMap<String, String>,
+    extensions: HashMap<String, String>,
+}
+
+fn parse_sign_args(args: &[String]) -> Result<SignArgs, String> {
+    let mut ca = None;
+    let mut pubkey_path = None;
+    let mut principals = Vec::new();
+    let mut valid_from = 0u64;
+    let mut valid_to = u64::MAX;
+    let mut critical_options = HashMap::new();
+    let mut extensions = HashMap::new();
+
+    let mut i = 0;
+    while i < args.len() {
+        let flag = &args[i];
+        let mut value = || {
+            i += 1;
+            args.get(i).cloned().ok_or_else(|| format!("{} expects a value", flag))
+        };
+
+        match flag.as_str() {
+            "--ca" => ca = Some(parse_slot(&value()?).ok_or("invalid --ca value; expected 0x<slot>")?),
+            "--pubkey" => pubkey_path = Some(value()?),
+            "--principal" => principals.push(value()?),
+            "--valid-from" => valid_from = value()?.parse().map_err(|_| "invalid --valid-from")?,
+            "--valid-to" => valid_to = value()?.parse().map_err(|_| "invalid --valid-to")?,
+            "--critical-option" => {
+                let (k, v) = split_kv(&value()?)?;
+                critical_options.insert(k, v);
+            }
+            "--extension" => {
+                let (k, v) = split_kv(&value()?)?;
+                extensions.insert(k, v);
+            }
+            _ => return Err(format!("unrecognized flag: {}", flag)),
+        }
+        i += 1;
+    }
+
+    Ok(SignArgs {
+        ca: ca.ok_or("--ca is required")?,
+        pubkey_path: pubkey_path.ok_or("--pubkey is required")?,
+        principals,
+        valid_from,
+        valid_to,
+        critical_options,
+        extensions,
+    })
+}
+
+fn split_kv(pair: &str) -> Result<(String, String), String> {
+    let mut parts = pair.splitn(2, '=');
+    let key = parts.next().ok_or("expected key=value")?;
+    let value = parts.next().unwrap_or("");
+    Ok((key.to_string(), value.to_string()))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn cmd_fingerprint() {
+    println!("Normal Slots:");
+    for slot in NORMAL_SLOTS.iter().map(|&b| SlotId::from(b)) {
+        print_slot_fingerprint(slot);
+    }
+
+    println!("Retired Slots:");
+    for slot in RETIRED_SLOTS.map(|b| SlotId::Retired(RetiredSlotId::from(b))) {
+        print_slot_fingerprint(slot);
+    }
+}
+
+fn print_slot_fingerprint(slot: SlotId) {
+    match (fetch_subject(slot), ssh_cert_fetch_pubkey(slot)) {
+        (Ok(subj), Some(cert)) => {
+            let attest = fetch_attestation(slot);
+            println!(
+                "\t{:?}:\t[Fingerprint: {}] [Attest: {}] Subject: [{}]",
+                slot,
+                cert.fingerprint().hash,
+                if attest.is_some() { "Yes" } else { "No" },
+                subj
+            )
+        }
+        _ => println!("\t{:?}:\tNo cert found", slot),
+    }
+}
+
+/// Builds the unsigned body of an OpenSSH certificate (everything up to but
+/// not including the CA's signature) for the key at `pubkey_path`.
+fn build_cert_body(args: &SignArgs) -> Result<Vec<u8>, String> {
+    let pubkey_bytes = fs::read(&args.pubkey_path).map_err(|e| format!("reading {}: {}", args.pubkey_path, e))?;
+
+    let mut writer = VecWriter::new();
+    writer.write_bytes(&pubkey_bytes).map_err(|e| e.to_string())?;
+    writer.write_string_vec(&args.principals).map_err(|e| e.to_string())?;
+    writer.write_u64(args.valid_from).map_err(|e| e.to_string())?;
+    writer.write_u64(args.valid_to).map_err(|e| e.to_string())?;
+    writer.write_string_map(&args.critical_options).map_err(|e| e.to_string())?;
+    writer.write_string_map(&args.extensions).map_err(|e| e.to_string())?;
+
+    Ok(writer.into_bytes())
+}
+
+fn cmd_sign(args: &[String]) {
+    let args = match parse_sign_args(args) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("sign: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let body = match build_cert_body(&args) {
+        Ok(body) => body,
+        Err(e) => {
+            eprintln!("sign: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    match ssh_cert_signer(args.ca, &body) {
+        Ok(signature) => {
+            let mut cert = VecWriter::new();
+            if cert.write_bytes(&body).and_then(|_| cert.write_bytes(&signature)).is_err() {
+                eprintln!("sign: failed to assemble signed certificate");
+                std::process::exit(1);
+            }
+            println!("{}", to_hex(&cert.into_bytes()));
+        }
+        Err(e) => {
+            eprintln!("sign: signing failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn cmd_attest(args: &[String]) {
+    let mut iter = args.iter();
+    let slot = match iter.next().map(String::as_str) {
+        Some("--slot") => match iter.next().and_then(|v| parse_slot(v)) {
+            Some(slot) => slot,
+            None => {
+                eprintln!("attest: --slot expects a value like 0x9c");
+                std::process::exit(1);
+            }
+        },
+        Some(other) => {
+            eprintln!("attest: unrecognized flag: {}", other);
+            std::process::exit(1);
+        }
+        None => {
+            eprintln!("attest: --slot is required");
+            std::process::exit(1);
+        }
+    };
+
+    match fetch_attestation(slot) {
+        Some(chain) => println!("{}", to_hex(&chain)),
+        None => {
+            eprintln!("attest: no attestation available for {:?}", slot);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("fingerprint") => cmd_fingerprint(),
+        Some("sign") => cmd_sign(&args[2..]),
+        Some("attest") => cmd_attest(&args[2..]),
+        _ => help(),
+    }
+}
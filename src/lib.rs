@@ -0,0 +1,14 @@
+//! With the `std` feature disabled (and `no-std` enabled instead) this crate
+//! builds on `core` + `alloc`, so certificate bodies can be assembled inside
+//! `no_std` firmware such as signing appliances and TEEs that only provide a
+//! global allocator.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod agent;
+pub mod ssh;
+#[cfg(feature = "std")]
+pub mod yubikey;
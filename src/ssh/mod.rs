@@ -0,0 +1,7 @@
+mod pubkey;
+mod reader;
+mod writer;
+
+pub use pubkey::{EcdsaCurve, EcdsaPublicKey, Ed25519PublicKey, PublicKey, PublicKeyKind, RsaPublicKey};
+pub use reader::{Error as ReadError, Readable, Reader};
+pub use writer::{CertVersion, Error, StringMap, VecWriter, Writeable, Writer};
@@ -0,0 +1,45 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// The curve used by an [`EcdsaPublicKey`].
+///
+/// Only the identifier (e.g. `nistp256`) is needed for serialization; the
+/// rest of the curve's parameters are implied by it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EcdsaCurve {
+    pub identifier: &'static str,
+}
+
+/// An ECDSA public key: the curve it is defined over and the encoded point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EcdsaPublicKey {
+    pub curve: EcdsaCurve,
+    pub key: Vec<u8>,
+}
+
+/// An RSA public key, represented as its modulus (`n`) and exponent (`e`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RsaPublicKey {
+    pub n: Vec<u8>,
+    pub e: Vec<u8>,
+}
+
+/// An Ed25519 public key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ed25519PublicKey {
+    pub key: Vec<u8>,
+}
+
+/// The key material for a `PublicKey`, one variant per supported algorithm.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PublicKeyKind {
+    Ecdsa(EcdsaPublicKey),
+    Rsa(RsaPublicKey),
+    Ed25519(Ed25519PublicKey),
+}
+
+/// A parsed OpenSSH public key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKey {
+    pub kind: PublicKeyKind,
+}
@@ -1,199 +1,295 @@
+#[cfg(feature = "std")]
 use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::io;
+
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap;
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
 
 use super::pubkey::{PublicKey, PublicKeyKind};
 
-use byteorder::{BigEndian, ByteOrder};
+/// The map written/read by [`Writer::write_string_map`], `HashMap` under the
+/// `std` feature and `BTreeMap` (no hashing, no `std` dependency) under
+/// `no-std`.
+#[cfg(feature = "std")]
+pub type StringMap = HashMap<String, String>;
+#[cfg(not(feature = "std"))]
+pub type StringMap = BTreeMap<String, String>;
 
-/// A `Writer` is used for encoding a key in OpenSSH compatible format.
-#[derive(Debug)]
-pub struct Writer {
-    inner: Vec<u8>,
+/// Which wire layout to encode a public key / certificate body as.
+///
+/// Adapted from librustzcash's `write_v4`/`write_v5` split: new key types or
+/// field orderings get a new variant and a new per-version write routine
+/// instead of changing what an existing version emits, so a caller pinned to
+/// `V1` keeps getting `V1`'s exact bytes even after `V2` is introduced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CertVersion {
+    #[default]
+    V1,
 }
 
-impl Writer {
-    /// Creates a new `Writer` instance.
-    ///
-    /// # Example
-    /// ```rust
-    /// # use rustica_keys::ssh::Writer;
-    /// let writer = Writer::new();
-    /// ```
-    pub fn new() -> Writer {
-        Writer { inner: Vec::new() }
-    }
+/// The error produced by a failed write.
+///
+/// Under the `std` feature this is `std::io::Error`, so a short write or a
+/// closed socket surfaces as the same error a caller would get from
+/// `std::io::Write` directly. Under `no-std`, writes only ever go into an
+/// in-memory [`VecWriter`] and cannot fail, so this is an empty marker type.
+#[cfg(feature = "std")]
+pub type Error = io::Error;
 
-    /// Writes a byte sequence to the underlying vector.
-    /// The value is represented as a the byte sequence length,
-    /// followed by the actual byte sequence.
-    ///
-    /// # Example
-    /// ```rust
-    /// # use rustica_keys::ssh::Writer;
-    /// let mut writer = Writer::new();
-    /// writer.write_bytes(&[0, 0, 0, 42]);
-    /// let bytes = writer.into_bytes();
-    /// assert_eq!(bytes, vec![0, 0, 0, 4, 0, 0, 0, 42]);
-    /// ```
-    pub fn write_bytes(&mut self, val: &[u8]) {
-        let size = val.len() as u32;
-        let mut buf = vec![0; 4];
-        BigEndian::write_u32(&mut buf, size);
-        self.inner.append(&mut buf);
-        self.inner.extend_from_slice(&val);
-    }
+/// See the `std` version of this type; writes to a `VecWriter` never fail
+/// under `no-std` since there is no underlying sink to reject them.
+#[cfg(not(feature = "std"))]
+#[derive(Debug)]
+pub struct Error;
 
-    /// Writes a `string` value to the underlying byte sequence.
-    ///
-    /// # Example
-    /// ```rust
-    /// # use rustica_keys::ssh::Writer;
-    /// let mut writer = Writer::new();
-    /// writer.write_string("a test string");
-    /// let bytes = writer.into_bytes();
-    /// assert_eq!(bytes, [0, 0, 0, 13, 97, 32, 116, 101, 115, 116, 32, 115, 116, 114, 105, 110, 103]);
-    /// ```
-    pub fn write_string(&mut self, val: &str) {
-        self.write_bytes(val.as_bytes());
-    }
+/// Encodes values in the OpenSSH wire format onto some underlying sink.
+///
+/// Every method returns `Result<(), Error>` so a short write or a closed
+/// socket surfaces as an error instead of panicking or forcing the whole
+/// certificate to be buffered in memory first. Under the `std` feature a
+/// blanket implementation is provided for any `std::io::Write`, so a
+/// `Writer` can target a `Vec<u8>`, a file, or a socket interchangeably.
+pub trait Writer {
+    /// Writes a single byte.
+    fn write_u8(&mut self, val: u8) -> Result<(), Error>;
 
-    /// Writes a `u64` value to the underlying byte sequence.
-    ///
-    /// # Example
-    /// ```rust
-    /// # use rustica_keys::ssh::Writer;
-    /// let mut writer = Writer::new();
-    /// writer.write_u64(0xFFFFFFFFFFFFFFFF);
-    /// let bytes = writer.into_bytes();
-    /// assert_eq!(bytes, [255, 255, 255, 255, 255, 255, 255, 255]);
-    /// ```
-    pub fn write_u64(&mut self, val: u64) {
-        let bytes = val.to_be_bytes();
-        self.inner.extend_from_slice(&bytes);
-    }
+    /// Writes a `u32` in network byte order.
+    fn write_u32(&mut self, val: u32) -> Result<(), Error>;
+
+    /// Writes a `u64` in network byte order.
+    fn write_u64(&mut self, val: u64) -> Result<(), Error>;
 
-    /// Writes a `u32` value to the underlying byte sequence.
+    /// Writes a byte sequence as its length followed by the bytes themselves.
+    fn write_bytes(&mut self, val: &[u8]) -> Result<(), Error>;
+
+    /// Writes a `string` value to the underlying sink.
     ///
     /// # Example
     /// ```rust
-    /// # use rustica_keys::ssh::Writer;
-    /// let mut writer = Writer::new();
-    /// writer.write_u32(0xFFFFFFFF);
+    /// # use rustica_keys::ssh::{VecWriter, Writer};
+    /// let mut writer = VecWriter::new();
+    /// writer.write_string("a test string").unwrap();
     /// let bytes = writer.into_bytes();
-    /// assert_eq!(bytes, [255, 255, 255, 255]);
+    /// assert_eq!(bytes, [0, 0, 0, 13, 97, 32, 116, 101, 115, 116, 32, 115, 116, 114, 105, 110, 103]);
     /// ```
-    pub fn write_u32(&mut self, val: u32) {
-        let bytes = val.to_be_bytes();
-        self.inner.extend_from_slice(&bytes);
+    fn write_string(&mut self, val: &str) -> Result<(), Error> {
+        self.write_bytes(val.as_bytes())
     }
 
-    /// Writes an `mpint` value to the underlying byte sequence.
+    /// Writes an `mpint` value to the underlying sink.
     /// If the MSB bit of the first byte is set then the number is
     /// negative, otherwise it is positive.
     /// Positive numbers must be preceeded by a leading zero byte according to RFC 4251, section 5.
     ///
     /// # Example
     /// ```rust
-    /// # use rustica_keys::ssh::Writer;
-    /// let mut writer = Writer::new();
-    /// writer.write_mpint(&[1, 0, 1]);
+    /// # use rustica_keys::ssh::{VecWriter, Writer};
+    /// let mut writer = VecWriter::new();
+    /// writer.write_mpint(&[1, 0, 1]).unwrap();
     /// let bytes = writer.into_bytes();
     /// assert_eq!(bytes, [0, 0, 0, 3, 1, 0, 1]);
     /// ```
-    pub fn write_mpint(&mut self, val: &[u8]) {
+    fn write_mpint(&mut self, val: &[u8]) -> Result<(), Error> {
         let mut bytes = val.to_vec();
 
         // If most significant bit is set then prepend a zero byte to
         // avoid interpretation as a negative number.
-        if val.get(0).unwrap_or(&0) & 0x80 != 0 {
+        if val.first().unwrap_or(&0) & 0x80 != 0 {
             bytes.insert(0, 0);
         }
 
-        self.write_bytes(&bytes);
+        self.write_bytes(&bytes)
     }
 
-    /// Writes a `Vec<String>` to the underlying byte sequence.
+    /// Writes a `Vec<String>` to the underlying sink.
     ///
     /// # Example
     /// ```rust
-    /// # use rustica_keys::ssh::Writer;
-    /// let mut writer = Writer::new();
-    /// 
-    /// writer.write_string_vec(&vec![String::from("Test"), String::from("Test")]);
+    /// # use rustica_keys::ssh::{VecWriter, Writer};
+    /// let mut writer = VecWriter::new();
+    ///
+    /// writer.write_string_vec(&vec![String::from("Test"), String::from("Test")]).unwrap();
     /// let bytes = writer.into_bytes();
     /// assert_eq!(bytes, [0, 0, 0, 16, 0, 0, 0, 4, 84, 101, 115, 116, 0, 0, 0, 4, 84, 101, 115, 116]);
     /// ```
-    pub fn write_string_vec(&mut self, vec: &[String]) {
-        let total_length = vec.iter().map(|x| x.len()).fold(vec.len()*4, |x, y| x + y) as u32;
-        self.write_u32(total_length);
+    fn write_string_vec(&mut self, vec: &[String]) -> Result<(), Error> {
+        let total_length = vec.iter().map(|x| x.len()).fold(vec.len() * 4, |x, y| x + y) as u32;
+        self.write_u32(total_length)?;
 
         for item in vec {
-            self.write_string(item);
+            self.write_string(item)?;
         }
+
+        Ok(())
     }
 
-    /// Writes a `HashMap<String, String>` to the underlying byte sequence.
+    /// Writes a [`StringMap`] to the underlying sink.
     ///
     /// # Example
     /// ```rust
-    /// # use rustica_keys::ssh::Writer;
-    /// # use std::collections::HashMap;
-    /// 
-    /// let mut writer = Writer::new();
-    /// let mut example_map = HashMap::new();
+    /// # use rustica_keys::ssh::{StringMap, VecWriter, Writer};
+    ///
+    /// let mut writer = VecWriter::new();
+    /// let mut example_map = StringMap::new();
     /// example_map.insert(String::from("Test"), String::from(""));
-    /// writer.write_string_map(&example_map);
+    /// writer.write_string_map(&example_map).unwrap();
     /// let bytes = writer.into_bytes();
     /// assert_eq!(bytes, [0, 0, 0, 12, 0, 0, 0, 4, 84, 101, 115, 116, 0, 0, 0, 0]);
     /// ```
-    pub fn write_string_map(&mut self, map: &HashMap<String, String>) {
-        let total_length = map.iter()
+    fn write_string_map(&mut self, map: &StringMap) -> Result<(), Error> {
+        let total_length = map
+            .iter()
             .map(|x| x.0.len() + x.1.len() + if !x.1.is_empty() { 4 } else { 0 })
             .fold(map.len() * 8, |x, y| x + y) as u32;
 
-        self.write_u32(total_length);
+        self.write_u32(total_length)?;
 
-        for (k,v) in map {
-            self.write_string(k);
+        for (k, v) in map {
+            self.write_string(k)?;
             if v.is_empty() {
-                self.write_u32(0x0);
+                self.write_u32(0x0)?;
             } else {
-                self.write_u32(v.len() as u32 + 4);
-                self.write_string(v);
+                self.write_u32(v.len() as u32 + 4)?;
+                self.write_string(v)?;
             }
         }
+
+        Ok(())
+    }
+
+    /// Writes a `PublicKey` to the underlying sink using the default
+    /// (current) [`CertVersion`]. A thin shim over
+    /// [`Writer::write_pub_key_versioned`] for callers that don't care
+    /// about pinning a specific wire layout.
+    fn write_pub_key(&mut self, key: &PublicKey) -> Result<(), Error>
+    where
+        Self: Sized,
+    {
+        self.write_pub_key_versioned(key, CertVersion::default())
     }
 
-    /// Writes a `PublicKey` to the underlying byte sequence.
+    /// Writes a `PublicKey` to the underlying sink using the given
+    /// [`CertVersion`]'s layout.
     ///
     /// # Example
+    /// ```rust
+    /// # use rustica_keys::ssh::{CertVersion, VecWriter, Writer};
+    /// # use rustica_keys::ssh::{Ed25519PublicKey, PublicKey, PublicKeyKind};
+    /// let key = PublicKey { kind: PublicKeyKind::Ed25519(Ed25519PublicKey { key: vec![1, 2, 3] }) };
+    /// let mut writer = VecWriter::new();
+    /// writer.write_pub_key_versioned(&key, CertVersion::V1).unwrap();
+    /// assert_eq!(writer.into_bytes(), [0, 0, 0, 3, 1, 2, 3]);
     /// ```
-    /// ```
-    pub fn write_pub_key(&mut self, key: &PublicKey) {
-         // Write the public key
-         match &key.kind {
-            PublicKeyKind::Ecdsa(key) => {
-                self.write_string(key.curve.identifier);
-                self.write_bytes(&key.key);
-            },
-            PublicKeyKind::Rsa(key) => {
-                self.write_bytes(&key.n);
-                self.write_bytes(&key.e);
+    fn write_pub_key_versioned(&mut self, key: &PublicKey, version: CertVersion) -> Result<(), Error>
+    where
+        Self: Sized,
+    {
+        key.write_to_versioned(self, version)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: io::Write> Writer for W {
+    fn write_u8(&mut self, val: u8) -> Result<(), Error> {
+        self.write_all(&[val])
+    }
+
+    fn write_u32(&mut self, val: u32) -> Result<(), Error> {
+        self.write_all(&val.to_be_bytes())
+    }
+
+    fn write_u64(&mut self, val: u64) -> Result<(), Error> {
+        self.write_all(&val.to_be_bytes())
+    }
+
+    fn write_bytes(&mut self, val: &[u8]) -> Result<(), Error> {
+        self.write_u32(val.len() as u32)?;
+        self.write_all(val)
+    }
+}
+
+/// A value that can encode itself onto any [`Writer`].
+///
+/// This is the counterpart to the free-standing `write_*` helpers on
+/// `Writer`: types that make up a certificate implement `Writeable` once,
+/// and get to be written to a `Vec`, a file, or a socket for free.
+pub trait Writeable {
+    fn write_to<W: Writer>(&self, writer: &mut W) -> Result<(), Error>;
+}
+
+impl Writeable for PublicKeyKind {
+    fn write_to<W: Writer>(&self, writer: &mut W) -> Result<(), Error> {
+        self.write_to_versioned(writer, CertVersion::default())
+    }
+}
+
+impl PublicKeyKind {
+    /// Per-version dispatch for encoding the key material. `V1` is the only
+    /// layout today; later versions get their own match arm here rather than
+    /// changing what `V1` emits.
+    fn write_to_versioned<W: Writer>(&self, writer: &mut W, version: CertVersion) -> Result<(), Error> {
+        match version {
+            CertVersion::V1 => match self {
+                PublicKeyKind::Ecdsa(key) => {
+                    writer.write_string(key.curve.identifier)?;
+                    writer.write_bytes(&key.key)
+                }
+                PublicKeyKind::Rsa(key) => {
+                    writer.write_bytes(&key.n)?;
+                    writer.write_bytes(&key.e)
+                }
+                PublicKeyKind::Ed25519(key) => writer.write_bytes(&key.key),
             },
-            PublicKeyKind::Ed25519(key) => {
-                self.write_bytes(&key.key);
-            }
-        };
+        }
+    }
+}
+
+impl Writeable for PublicKey {
+    fn write_to<W: Writer>(&self, writer: &mut W) -> Result<(), Error> {
+        self.write_to_versioned(writer, CertVersion::default())
     }
+}
 
-    /// Converts the `Writer` into a byte sequence.
-    /// This consumes the underlying byte sequence used by the `Writer`.
+impl PublicKey {
+    fn write_to_versioned<W: Writer>(&self, writer: &mut W, version: CertVersion) -> Result<(), Error> {
+        self.kind.write_to_versioned(writer, version)
+    }
+}
+
+/// An in-memory [`Writer`] backed by a `Vec<u8>`, kept around for the
+/// common case where the whole certificate is built up before being sent
+/// or written out elsewhere. This is the only sink available under `no-std`.
+#[derive(Debug, Default)]
+pub struct VecWriter {
+    inner: Vec<u8>,
+}
+
+impl VecWriter {
+    /// Creates a new `VecWriter` instance.
     ///
     /// # Example
     /// ```rust
-    /// # use rustica_keys::ssh::Writer;
-    /// 
-    /// let mut writer = Writer::new();
-    /// writer.write_string("some data");
+    /// # use rustica_keys::ssh::VecWriter;
+    /// let writer = VecWriter::new();
+    /// ```
+    pub fn new() -> VecWriter {
+        VecWriter { inner: Vec::new() }
+    }
+
+    /// Converts the `VecWriter` into a byte sequence.
+    /// This consumes the underlying byte sequence used by the `VecWriter`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use rustica_keys::ssh::{VecWriter, Writer};
+    ///
+    /// let mut writer = VecWriter::new();
+    /// writer.write_string("some data").unwrap();
     /// let bytes = writer.into_bytes();
     /// assert_eq!(bytes, [0, 0, 0, 9, 115, 111, 109, 101, 32, 100, 97, 116, 97]);
     /// ```
@@ -201,16 +297,15 @@ impl Writer {
         self.inner
     }
 
-    /// Converts the `Writer` into a byte sequence.
-    /// This consumes the underlying byte sequence used by the `Writer`.
+    /// Returns the bytes written so far without consuming the `VecWriter`.
     ///
     /// # Example
     /// ```rust
-    /// # use rustica_keys::ssh::Writer;
-    /// 
-    /// let mut writer = Writer::new();
-    /// writer.write_string("some data");
-    /// let bytes = writer.into_bytes();
+    /// # use rustica_keys::ssh::{VecWriter, Writer};
+    ///
+    /// let mut writer = VecWriter::new();
+    /// writer.write_string("some data").unwrap();
+    /// let bytes = writer.as_bytes();
     /// assert_eq!(bytes, [0, 0, 0, 9, 115, 111, 109, 101, 32, 100, 97, 116, 97]);
     /// ```
     pub fn as_bytes(&self) -> &[u8] {
@@ -218,8 +313,37 @@ impl Writer {
     }
 }
 
-impl Default for Writer {
-    fn default() -> Self {
-        Writer::new()
+#[cfg(feature = "std")]
+impl io::Write for VecWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl Writer for VecWriter {
+    fn write_u8(&mut self, val: u8) -> Result<(), Error> {
+        self.inner.push(val);
+        Ok(())
+    }
+
+    fn write_u32(&mut self, val: u32) -> Result<(), Error> {
+        self.inner.extend_from_slice(&val.to_be_bytes());
+        Ok(())
     }
-}
\ No newline at end of file
+
+    fn write_u64(&mut self, val: u64) -> Result<(), Error> {
+        self.inner.extend_from_slice(&val.to_be_bytes());
+        Ok(())
+    }
+
+    fn write_bytes(&mut self, val: &[u8]) -> Result<(), Error> {
+        self.write_u32(val.len() as u32)?;
+        self.inner.extend_from_slice(val);
+        Ok(())
+    }
+}
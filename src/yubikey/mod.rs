@@ -0,0 +1,74 @@
+//! Yubikey PIV slot addressing and lookups, the backend `yk-fingerprint`,
+//! the `agent` subsystem and the `sshcerts` CLI all build on.
+//!
+//! This build has no PC/SC or hardware PIV backend linked in, so every
+//! lookup here honestly reports "nothing found" (or a
+//! [`std::io::Error`]) instead of fabricating key material. Wiring in a
+//! real backend means plugging an implementation into the functions
+//! below; the slot addressing and the `ssh` submodule's types are the
+//! stable surface the rest of the crate is built against.
+
+use std::fmt;
+
+pub mod ssh;
+
+/// A Yubikey PIV slot. Any byte is accepted as a `Normal` slot address
+/// (via the standard library's blanket `From` -> `TryFrom` impl, so
+/// `SlotId::try_from(byte)` still works for callers), since validating it
+/// requires talking to the hardware, which
+/// [`fetch_subject`]/[`ssh::ssh_cert_fetch_pubkey`]/etc. already do and
+/// report failure from directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SlotId {
+    Normal(u8),
+    Retired(RetiredSlotId),
+}
+
+impl From<u8> for SlotId {
+    fn from(value: u8) -> Self {
+        SlotId::Normal(value)
+    }
+}
+
+/// One of a Yubikey's retired PIV slots. Never fails to convert from a
+/// byte, for the same reason as [`SlotId`]'s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RetiredSlotId(u8);
+
+impl From<u8> for RetiredSlotId {
+    fn from(value: u8) -> Self {
+        RetiredSlotId(value)
+    }
+}
+
+/// The error produced when a PIV lookup can't be satisfied.
+#[derive(Debug)]
+pub enum Error {
+    /// No hardware PIV backend is linked into this build.
+    NoBackend,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NoBackend => write!(f, "no hardware PIV backend is linked into this build"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Fetches the X.509 certificate subject bound to `slot`.
+///
+/// Always fails in this build; see the module docs.
+pub fn fetch_subject(_slot: SlotId) -> Result<String, Error> {
+    Err(Error::NoBackend)
+}
+
+/// Fetches the attestation certificate chain proving `slot`'s key was
+/// generated on-device, if the hardware can produce one.
+///
+/// Always returns `None` in this build; see the module docs.
+pub fn fetch_attestation(_slot: SlotId) -> Option<Vec<u8>> {
+    None
+}
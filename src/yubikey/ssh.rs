@@ -0,0 +1,55 @@
+//! The slice of the Yubikey backend that deals in SSH key material:
+//! fetching a slot's certificate as a [`PublicKey`] and signing with it.
+
+use std::io;
+
+use super::SlotId;
+use crate::ssh::PublicKey;
+
+/// A human-readable fingerprint for a [`Certificate`]'s public key.
+pub struct Fingerprint {
+    pub hash: String,
+}
+
+/// An SSH certificate/public key read off a Yubikey PIV slot.
+pub struct Certificate {
+    pub key: PublicKey,
+}
+
+impl Certificate {
+    /// Computes this certificate's fingerprint.
+    ///
+    /// Real OpenSSH fingerprints are `SHA256:<base64>` over the key's wire
+    /// encoding; this build has no hash backend linked in, so this hex-encodes
+    /// the raw encoding instead. Since [`ssh_cert_fetch_pubkey`] never
+    /// produces a `Certificate` without a hardware backend, this is here
+    /// purely so the type is complete, not because it's reachable today.
+    pub fn fingerprint(&self) -> Fingerprint {
+        use crate::ssh::{VecWriter, Writer};
+
+        let mut writer = VecWriter::new();
+        let hash = match writer.write_pub_key(&self.key) {
+            Ok(()) => writer.into_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
+            Err(_) => String::new(),
+        };
+
+        Fingerprint { hash }
+    }
+}
+
+/// Fetches the certificate/public key stored in `slot`.
+///
+/// Always returns `None` in this build: no hardware PIV backend is linked
+/// in, see the [`super`] module docs.
+pub fn ssh_cert_fetch_pubkey(_slot: SlotId) -> Option<Certificate> {
+    None
+}
+
+/// Asks the Yubikey in `slot` to sign `data`, without the private key ever
+/// leaving the device.
+///
+/// Always fails in this build: no hardware PIV backend is linked in, see
+/// the [`super`] module docs.
+pub fn ssh_cert_signer(_slot: SlotId, _data: &[u8]) -> io::Result<Vec<u8>> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "no hardware PIV signing backend is linked into this build"))
+}